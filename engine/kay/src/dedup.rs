@@ -0,0 +1,77 @@
+use std::collections::{HashSet, VecDeque};
+use super::type_registry::ShortTypeId;
+
+/// How many recently-seen `(type_id, key)` pairs to remember in total, across
+/// all recipient types, before the oldest ones are forgotten again
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// A bounded cache of recently-seen `DedupID` keys, keyed by recipient type,
+/// used to drop a second delivery of an already-seen `(type_id, key)` pair.
+/// The bound is shared by all recipient types rather than applied to each
+/// type separately, so keys of one busy type can evict those of another.
+pub struct RecentlySeen {
+    capacity: usize,
+    seen: HashSet<(ShortTypeId, u64)>,
+    order: VecDeque<(ShortTypeId, u64)>,
+}
+
+impl Default for RecentlySeen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecentlySeen {
+    /// Create a cache with the default capacity
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create a cache that remembers at most `capacity` keys
+    pub fn with_capacity(capacity: usize) -> Self {
+        RecentlySeen {
+            capacity,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Record `key` for `type_id` and return `true` if it's new, or `false`
+    /// if it was already seen (and should therefore be dropped rather than
+    /// delivered to the handler)
+    pub fn insert_and_check_new(&mut self, type_id: ShortTypeId, key: u64) -> bool {
+        let entry = (type_id, key);
+        if !self.seen.insert(entry) {
+            return false;
+        }
+
+        self.order.push_back(entry);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_key_is_rejected_until_evicted() {
+        let type_id = ShortTypeId::new(1);
+        let mut seen = RecentlySeen::with_capacity(2);
+
+        assert!(seen.insert_and_check_new(type_id, 1));
+        assert!(!seen.insert_and_check_new(type_id, 1));
+
+        // pushes key `1` past the capacity boundary, evicting it
+        assert!(seen.insert_and_check_new(type_id, 2));
+        assert!(seen.insert_and_check_new(type_id, 3));
+
+        assert!(seen.insert_and_check_new(type_id, 1));
+    }
+}
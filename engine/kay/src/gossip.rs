@@ -0,0 +1,168 @@
+use std::collections::{HashMap, HashSet};
+use super::id::MachineID;
+
+/// Identifies a single global-broadcast message, regardless of how many
+/// machines it has already passed through
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MessageID {
+    /// The machine that originally sent the broadcast
+    pub origin_machine: MachineID,
+    /// A per-origin-machine sequence number, assigned by the origin
+    pub sequence: u64,
+}
+
+/// A Plumtree (epidemic broadcast tree) for `global_broadcast` delivery:
+/// payloads are eagerly forwarded along a spanning-tree "eager push" peer
+/// set, while the remaining "lazy push" peers only get told the message id,
+/// GRAFTing in or PRUNEing links as duplicates and misses are discovered.
+#[derive(Default)]
+pub struct Plumtree {
+    eager_peers: HashSet<MachineID>,
+    lazy_peers: HashSet<MachineID>,
+    seen: HashSet<MessageID>,
+}
+
+impl Plumtree {
+    /// Start a tree with no peers yet
+    pub fn new() -> Self {
+        Plumtree {
+            eager_peers: HashSet::new(),
+            lazy_peers: HashSet::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Add a newly discovered machine as an eager peer, i.e. a tentative tree
+    /// edge, until a PRUNE demotes it
+    pub fn add_peer(&mut self, machine: MachineID) {
+        self.eager_peers.insert(machine);
+    }
+
+    /// Forget a peer entirely, e.g. because the machine dropped out of the
+    /// cluster
+    pub fn remove_peer(&mut self, machine: MachineID) {
+        self.eager_peers.remove(&machine);
+        self.lazy_peers.remove(&machine);
+    }
+
+    /// The peers a full payload should be eagerly forwarded to, excluding
+    /// `from` (the peer we just received it from, if any)
+    pub fn eager_push_targets(&self, from: Option<MachineID>) -> Vec<MachineID> {
+        self.eager_peers
+            .iter()
+            .cloned()
+            .filter(|peer| Some(*peer) != from)
+            .collect()
+    }
+
+    /// The peers that should only be told the compact message id, excluding
+    /// `from`
+    pub fn lazy_push_targets(&self, from: Option<MachineID>) -> Vec<MachineID> {
+        self.lazy_peers
+            .iter()
+            .cloned()
+            .filter(|peer| Some(*peer) != from)
+            .collect()
+    }
+
+    /// Handle a full payload eagerly pushed by `from`. Returns `true` the
+    /// first time `message` is seen, or `false` for a duplicate, in which
+    /// case `from` is PRUNEd to a lazy peer.
+    pub fn receive_eager(&mut self, from: MachineID, message: MessageID) -> bool {
+        if self.seen.insert(message) {
+            true
+        } else {
+            self.prune(from);
+            false
+        }
+    }
+
+    /// Handle a lazily-gossiped id from `from`. Returns `true` if this
+    /// machine hasn't seen `message` yet, in which case the link to `from` is
+    /// GRAFTed into the tree and the caller should request the full payload
+    /// from `from`.
+    pub fn receive_lazy_id(&mut self, from: MachineID, message: MessageID) -> bool {
+        if self.seen.contains(&message) {
+            false
+        } else {
+            self.graft(from);
+            true
+        }
+    }
+
+    /// Promote a lazy peer to an eager one, making it a tree edge
+    fn graft(&mut self, peer: MachineID) {
+        self.lazy_peers.remove(&peer);
+        self.eager_peers.insert(peer);
+    }
+
+    /// Demote an eager peer to a lazy one, removing a redundant tree edge
+    fn prune(&mut self, peer: MachineID) {
+        self.eager_peers.remove(&peer);
+        self.lazy_peers.insert(peer);
+    }
+
+    /// Record that `message` has been (or is about to be) delivered locally,
+    /// e.g. because this machine is the origin
+    pub fn mark_seen(&mut self, message: MessageID) -> bool {
+        self.seen.insert(message)
+    }
+}
+
+/// Assigns ever-increasing per-machine sequence numbers for originating new
+/// global broadcasts
+#[derive(Default)]
+pub struct SequenceCounter(HashMap<MachineID, u64>);
+
+impl SequenceCounter {
+    /// Start with no sequence numbers handed out yet
+    pub fn new() -> Self {
+        SequenceCounter(HashMap::new())
+    }
+
+    /// Get the next `MessageID` to use for a broadcast originating on `origin`
+    pub fn next(&mut self, origin: MachineID) -> MessageID {
+        let sequence = self.0.entry(origin).or_insert(0);
+        let message = MessageID {
+            origin_machine: origin,
+            sequence: *sequence,
+        };
+        *sequence += 1;
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_eager_payload_prunes_sender_to_lazy() {
+        let mut tree = Plumtree::new();
+        let peer = MachineID(1);
+        let message = MessageID {
+            origin_machine: MachineID(0),
+            sequence: 0,
+        };
+        tree.add_peer(peer);
+
+        assert!(tree.receive_eager(peer, message));
+        assert!(!tree.receive_eager(peer, message));
+        assert!(tree.lazy_push_targets(None).contains(&peer));
+        assert!(!tree.eager_push_targets(None).contains(&peer));
+    }
+
+    #[test]
+    fn unseen_lazy_id_grafts_sender_to_eager() {
+        let mut tree = Plumtree::new();
+        let peer = MachineID(1);
+        let message = MessageID {
+            origin_machine: MachineID(0),
+            sequence: 0,
+        };
+
+        assert!(tree.receive_lazy_id(peer, message));
+        assert!(tree.eager_push_targets(None).contains(&peer));
+        assert!(!tree.lazy_push_targets(None).contains(&peer));
+    }
+}
@@ -1,14 +1,43 @@
 use super::type_registry::ShortTypeId;
+#[cfg(feature = "serde-serialization")]
+use serde_derive::{Deserialize, Serialize};
+
+/// An ID for the machine in a computing cluster or multiplayer environment
+/// that an `Actor`/`SubActor` lives on. Kept as its own newtype, rather than
+/// a bare `u8`, so it can carry its own serialization and isn't mixed up with
+/// unrelated byte fields once IDs start crossing the network.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde-serialization", derive(Serialize, Deserialize))]
+pub struct MachineID(pub u8);
+
+impl From<u8> for MachineID {
+    fn from(raw: u8) -> Self {
+        MachineID(raw)
+    }
+}
+
+impl From<MachineID> for u8 {
+    fn from(machine: MachineID) -> Self {
+        machine.0
+    }
+}
+
+impl ::std::fmt::Display for MachineID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 /// An ID that uniquely identifies an `Actor`, or even a `SubActor` within a `Swarm`
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-serialization", derive(Serialize, Deserialize))]
 pub struct ID {
     /// An ID for the type of the identified `Actor`, used to dispatch messages
     /// to the message handling functions registered for this type
     pub type_id: ShortTypeId,
     /// For future use: ID for the machine in a computing cluster
     /// or multiplayer environment that the identified `Actor` lives on
-    pub machine: u8,
+    pub machine: MachineID,
     /// For future use: allows safe reuse of an ID after `Actor`/`SubActor` death.
     /// The version is incremented to make the new (otherwise same) ID distinguishable
     /// from erroneous references to the `Actor`/`SubActor` previously identified
@@ -22,13 +51,13 @@ pub fn broadcast_sub_actor_id() -> u32 {
     u32::max_value()
 }
 
-pub fn broadcast_machine_id() -> u8 {
-    u8::max_value()
+pub fn broadcast_machine_id() -> MachineID {
+    MachineID(u8::max_value())
 }
 
 impl ID {
     /// Create a new ID
-    pub fn new(type_id: ShortTypeId, sub_actor_id: u32, machine: u8, version: u8) -> Self {
+    pub fn new(type_id: ShortTypeId, sub_actor_id: u32, machine: MachineID, version: u8) -> Self {
         ID {
             type_id: type_id,
             machine: machine,
@@ -64,6 +93,136 @@ impl ID {
     pub fn is_global_broadcast(&self) -> bool {
         self.machine == broadcast_machine_id()
     }
+
+    /// Check whether this ID's version differs from `current_version`, i.e. it
+    /// refers to a slot that has since been freed and reused.
+    pub fn is_stale_against(&self, current_version: u8) -> bool {
+        self.version != current_version
+    }
+
+    /// Pair this (typically broadcast) ID with a deterministic dedup key,
+    /// usually a hash of the message payload. Recipients can keep a bounded
+    /// recently-seen cache of these keys and drop a second arrival of what is
+    /// logically the same broadcast before it reaches the handler, without
+    /// changing the behavior of existing, un-keyed broadcasts.
+    pub fn with_dedup_key(&self, key: u64) -> DedupID {
+        DedupID { id: *self, key }
+    }
+
+    /// Get a local-broadcast version of this ID that the dispatch loop will
+    /// deliver to every machine-local instance except `exclude`. Removes the
+    /// self-filtering boilerplate that every "notify all my peers" handler
+    /// would otherwise need to do by hand.
+    pub fn broadcast_excluding(&self, exclude: ID) -> BroadcastExcludingID {
+        BroadcastExcludingID {
+            id: self.local_broadcast(),
+            exclude,
+        }
+    }
+
+    /// Like `broadcast_excluding`, but delivered globally (to every instance
+    /// on every machine) rather than just machine-locally.
+    pub fn global_broadcast_excluding(&self, exclude: ID) -> BroadcastExcludingID {
+        BroadcastExcludingID {
+            id: self.global_broadcast(),
+            exclude,
+        }
+    }
+
+    /// Encode this ID as a stable, canonical string that round-trips through
+    /// `from_raw_str`. Used wherever an actor needs to be named as text:
+    /// logging, save files, scripting consoles, network protocols.
+    pub fn as_raw_string(&self) -> String {
+        format!(
+            "@{}_?{}_#{}_v{}",
+            self.machine.0,
+            u16::from(self.type_id),
+            self.sub_actor_id,
+            self.version,
+        )
+    }
+
+    /// Parse the canonical string form produced by `as_raw_string` back into
+    /// an `ID`.
+    pub fn from_raw_str(s: &str) -> Result<ID, ParseIDError> {
+        let rest = s.strip_prefix('@').ok_or(ParseIDError::Malformed)?;
+
+        let (machine_str, rest) = split_once(rest, "_?").ok_or(ParseIDError::Malformed)?;
+        let (type_str, rest) = split_once(rest, "_#").ok_or(ParseIDError::Malformed)?;
+        let (sub_actor_str, version_str) = split_once(rest, "_v").ok_or(ParseIDError::Malformed)?;
+
+        let machine = machine_str
+            .parse::<u8>()
+            .map_err(|_| ParseIDError::InvalidField("machine"))?;
+        let type_id_raw = type_str
+            .parse::<u16>()
+            .map_err(|_| ParseIDError::InvalidField("type_id"))?;
+        let sub_actor_id = sub_actor_str
+            .parse::<u32>()
+            .map_err(|_| ParseIDError::InvalidField("sub_actor_id"))?;
+        let version = version_str
+            .parse::<u8>()
+            .map_err(|_| ParseIDError::InvalidField("version"))?;
+
+        Ok(ID::new(
+            ShortTypeId::new(type_id_raw),
+            sub_actor_id,
+            MachineID(machine),
+            version,
+        ))
+    }
+}
+
+fn split_once<'a>(s: &'a str, sep: &str) -> Option<(&'a str, &'a str)> {
+    let idx = s.find(sep)?;
+    Some((&s[..idx], &s[idx + sep.len()..]))
+}
+
+/// The ways `ID::from_raw_str` can fail to parse a canonical ID string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseIDError {
+    /// The string doesn't match the `@{machine}_?{type}_#{sub_actor}_v{version}` shape at all
+    Malformed,
+    /// A field was present but not a valid number for its type
+    InvalidField(&'static str),
+}
+
+impl ::std::fmt::Display for ParseIDError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            ParseIDError::Malformed => write!(f, "malformed ID string"),
+            ParseIDError::InvalidField(field) => write!(f, "invalid {} field in ID string", field),
+        }
+    }
+}
+
+impl ::std::error::Error for ParseIDError {}
+
+/// An `ID` (usually a broadcast) paired with a caller-chosen dedup key, so the
+/// dispatch layer can recognize and drop duplicate deliveries of what is
+/// logically the same broadcast, e.g. one that originated from more than one
+/// redundant cluster node or was replayed as input.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde-serialization", derive(Serialize, Deserialize))]
+pub struct DedupID {
+    /// The (usually broadcast) ID this message is addressed to
+    pub id: ID,
+    /// A key derived by the sender from the message content, e.g. a hash of
+    /// the payload
+    pub key: u64,
+}
+
+/// A broadcast `ID` paired with the `ID` of one instance to skip delivery to,
+/// e.g. the actor that triggered the broadcast in the first place. The
+/// dispatch loop delivers to every instance the broadcast addresses except
+/// `exclude`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde-serialization", derive(Serialize, Deserialize))]
+pub struct BroadcastExcludingID {
+    /// The broadcast ID to deliver to every instance it addresses, bar one
+    pub id: ID,
+    /// The one instance to skip delivery to
+    pub exclude: ID,
 }
 
 impl ::std::fmt::Debug for ID {
@@ -71,10 +230,51 @@ impl ::std::fmt::Debug for ID {
         write!(
             f,
             "ID @{}_?{}_#{}_v{}",
-            self.machine,
+            self.machine.0,
             u16::from(self.type_id),
             self.sub_actor_id,
             self.version,
         )
     }
 }
+
+#[cfg(all(test, feature = "serde-serialization"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn id_round_trips_through_serde_json() {
+        let id = ID::new(ShortTypeId::new(42), 7, MachineID(3), 5);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(serde_json::from_str::<ID>(&json).unwrap(), id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_raw_string_round_trips() {
+        let id = ID::new(ShortTypeId::new(42), 7, MachineID(3), 5);
+        assert_eq!(ID::from_raw_str(&id.as_raw_string()), Ok(id));
+    }
+
+    #[test]
+    fn from_raw_str_rejects_malformed_input() {
+        assert_eq!(ID::from_raw_str("not an id"), Err(ParseIDError::Malformed));
+        assert_eq!(ID::from_raw_str("@1_?2_#3"), Err(ParseIDError::Malformed));
+    }
+
+    #[test]
+    fn from_raw_str_rejects_out_of_range_fields() {
+        assert_eq!(
+            ID::from_raw_str("@256_?1_#1_v1"),
+            Err(ParseIDError::InvalidField("machine"))
+        );
+        assert_eq!(
+            ID::from_raw_str("@1_?1_#1_v256"),
+            Err(ParseIDError::InvalidField("version"))
+        );
+    }
+}
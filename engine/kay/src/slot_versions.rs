@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use super::id::ID;
+use super::type_registry::ShortTypeId;
+
+/// Tracks the current `version` for every `(type_id, sub_actor_id)` slot a
+/// `Swarm` (or single `Actor`) hands out IDs for
+#[derive(Default)]
+pub struct SlotVersions {
+    versions: HashMap<(ShortTypeId, u32), u8>,
+}
+
+impl SlotVersions {
+    /// Create an empty slot-version table
+    pub fn new() -> Self {
+        SlotVersions {
+            versions: HashMap::new(),
+        }
+    }
+
+    /// Get the current version of a slot, defaulting to `0` for a slot that
+    /// has never been freed and reused
+    pub fn current_version(&self, type_id: ShortTypeId, sub_actor_id: u32) -> u8 {
+        *self.versions.get(&(type_id, sub_actor_id)).unwrap_or(&0)
+    }
+
+    /// Record that a slot was freed and is about to be reused, bumping its
+    /// version. Returns the new version to stamp onto the slot's next occupant.
+    pub fn free_and_bump(&mut self, type_id: ShortTypeId, sub_actor_id: u32) -> u8 {
+        let version = self.versions.entry((type_id, sub_actor_id)).or_insert(0);
+        *version = version.wrapping_add(1);
+        *version
+    }
+
+    /// Check whether `id` is stale, i.e. no longer matches the current
+    /// version of the slot it addresses. Broadcast IDs don't address a single
+    /// slot, so they're never considered stale here.
+    pub fn is_stale(&self, id: ID) -> bool {
+        !id.is_broadcast() && id.is_stale_against(self.current_version(id.type_id, id.sub_actor_id))
+    }
+}
@@ -0,0 +1,111 @@
+use super::id::{broadcast_machine_id, MachineID, ID};
+use super::actor_system::ActorOrActorTrait;
+
+/// A compile-time typed handle for an `Actor`/`ActorTrait` instance, backed by a
+/// raw, untyped `ID`.
+///
+/// Without this, user code has to pass around bare `ID`s, and nothing stops a
+/// message meant for one actor class from being sent to an `ID` that actually
+/// refers to a completely different one. Implementors are simple newtype
+/// wrappers around `ID`, generated by the `TypedID!` macro, and should be the
+/// only thing user code deals in - the underlying `ID` stays an implementation
+/// detail used by the dispatch machinery.
+pub trait TypedID
+where
+    Self: Copy + Clone + PartialEq + Eq + ::std::hash::Hash + ::std::fmt::Debug + Sized,
+{
+    /// The `Actor`/`ActorTrait` that this typed ID can be used to address
+    type Target: ActorOrActorTrait;
+
+    /// Wrap a raw `ID` as this typed ID, trusting the caller that it actually
+    /// refers to an instance of `Target`
+    fn from_raw(raw: ID) -> Self;
+    /// Get back the raw, untyped `ID` underlying this typed ID
+    fn as_raw(&self) -> ID;
+
+    /// Address the first (and possibly only) machine-local instance of `Target`
+    fn local_first() -> Self {
+        Self::from_raw(ID::new(Self::Target::local_type_id(), 0, MachineID(0), 0))
+    }
+
+    /// Address the first (and possibly only) instance of `Target`, wherever in
+    /// the cluster it lives
+    fn global_first() -> Self {
+        Self::from_raw(ID::new(Self::Target::local_type_id(), 0, broadcast_machine_id(), 0))
+    }
+
+    /// Address all machine-local instances of `Target` at once
+    fn local_broadcast() -> Self {
+        Self::from_raw(Self::local_first().as_raw().local_broadcast())
+    }
+
+    /// Address all instances of `Target` on every machine at once
+    fn global_broadcast() -> Self {
+        Self::from_raw(Self::local_first().as_raw().global_broadcast())
+    }
+}
+
+/// Generate a newtype wrapper around `ID` that implements `TypedID` for
+/// `$target`, e.g. `TypedID!(RoadID, Road);` generates a `RoadID` that can only
+/// be used to address a `Road`.
+#[macro_export]
+macro_rules! TypedID {
+    ($id_name:ident, $target:ty) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct $id_name($crate::id::ID);
+
+        impl $crate::typed_id::TypedID for $id_name {
+            type Target = $target;
+
+            fn from_raw(raw: $crate::id::ID) -> Self {
+                $id_name(raw)
+            }
+
+            fn as_raw(&self) -> $crate::id::ID {
+                self.0
+            }
+        }
+
+        impl ::std::fmt::Debug for $id_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "{}({:?})", stringify!($id_name), self.0)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::type_registry::ShortTypeId;
+
+    struct TestActor;
+
+    impl ActorOrActorTrait for TestActor {
+        fn local_type_id() -> ShortTypeId {
+            ShortTypeId::new(7)
+        }
+    }
+
+    TypedID!(TestActorID, TestActor);
+
+    #[test]
+    fn as_raw_and_from_raw_round_trip() {
+        let raw = ID::new(ShortTypeId::new(7), 3, MachineID(1), 0);
+        assert_eq!(TestActorID::from_raw(raw).as_raw(), raw);
+    }
+
+    #[test]
+    fn local_first_addresses_the_target_on_this_machine() {
+        let id = TestActorID::local_first().as_raw();
+        assert_eq!(id.type_id, TestActor::local_type_id());
+        assert_eq!(id.machine, MachineID(0));
+        assert_eq!(id.sub_actor_id, 0);
+    }
+
+    #[test]
+    fn broadcast_helpers_set_the_broadcast_sentinels() {
+        assert!(TestActorID::local_broadcast().as_raw().is_broadcast());
+        assert!(TestActorID::global_broadcast().as_raw().is_global_broadcast());
+    }
+}